@@ -1,7 +1,16 @@
-use std::{collections::VecDeque, fmt};
+use std::{
+    collections::{HashMap, VecDeque},
+    fmt,
+    io::{self, Read, Write},
+    ops::RangeInclusive,
+};
 
 const ASCII_ALPHABET: &'static str = r#" 1234567890!@#$%^&*()`~-_=+abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ[]{}\|;:'",.<>/?"#;
 
+/// Number of characters processed per chunk by [`OneTimePad::encode_stream`]
+/// and [`OneTimePad::decode_stream`].
+const STREAM_CHUNK_CHARS: usize = 4096;
+
 type Result<T> = std::result::Result<T, OneTimePadError>;
 
 /// Possible errors whilst working with one time pads.
@@ -16,6 +25,26 @@ pub enum OneTimePadError {
     /// cannot be processed. If you need to use this character, initialise a
     /// new [`OneTimePad`] with the `new_with_alphabet` function.
     CharacterNotInAlphabet(char),
+
+    /// An I/O error occurred whilst streaming data through a one time pad
+    /// with [`OneTimePad::encode_stream`] or [`OneTimePad::decode_stream`].
+    Io(io::Error),
+
+    /// A stream passed to [`OneTimePad::encode_stream`] or
+    /// [`OneTimePad::decode_stream`] contained bytes that are not valid
+    /// UTF-8.
+    InvalidUtf8(std::str::Utf8Error),
+
+    /// The alphabet described by an [`AlphabetSpec`] is not valid, either
+    /// because it contains a duplicate symbol (which would make decoding
+    /// ambiguous) or because it contains no symbols at all.
+    InvalidAlphabet(String),
+
+    /// A base64-encoded ciphertext or pad passed to
+    /// [`OneTimePad::decode_from_base64`] or
+    /// [`OneTimePad::push_to_pad_from_base64`] was not valid base64.
+    #[cfg(feature = "base64")]
+    Base64(base64::DecodeError),
 }
 
 impl fmt::Display for OneTimePadError {
@@ -29,6 +58,11 @@ impl fmt::Display for OneTimePadError {
                 f,
                 "The character '{ch}' is not in the alphabet of this one time pad."
             ),
+            Self::Io(e) => write!(f, "an I/O error occurred: {e}"),
+            Self::InvalidUtf8(e) => write!(f, "the stream did not contain valid UTF-8: {e}"),
+            Self::InvalidAlphabet(reason) => write!(f, "invalid alphabet: {reason}"),
+            #[cfg(feature = "base64")]
+            Self::Base64(e) => write!(f, "invalid base64: {e}"),
         }
     }
 }
@@ -42,6 +76,120 @@ pub struct EncodingResult {
     pub pad: String,
 }
 
+/// The result of a byte-oriented encoding operation from
+/// [`OneTimePad::encode_bytes`].
+#[derive(Clone, Debug)]
+pub struct BytesEncodingResult {
+    /// The cipher bytes produced from the encoding operation.
+    pub cipher_bytes: Vec<u8>,
+    /// The pad data used in the encoding operation.
+    pub pad: Vec<u8>,
+}
+
+/// The result of a byte-oriented encoding operation, base64-encoded for safe
+/// transmission as text, from [`OneTimePad::encode_to_base64`].
+#[cfg(feature = "base64")]
+#[derive(Clone, Debug)]
+pub struct Base64EncodingResult {
+    /// The base64-encoded cipher text produced from the encoding operation.
+    pub cipher_text: String,
+    /// The base64-encoded pad data used in the encoding operation.
+    pub pad: String,
+}
+
+/// A builder for the symbol set of a one time pad's alphabet.
+///
+/// Symbols are added in the order they should be numbered, starting at 0.
+/// Passing the spec to [`OneTimePad::new_with_spec`] validates the set up
+/// front, so that a duplicated symbol (which would make decoding ambiguous,
+/// since the first match would always win) or an empty alphabet is caught at
+/// construction time rather than surfacing as a confusing encode/decode
+/// failure later.
+///
+/// ```
+/// use onetimepad::{AlphabetSpec, OneTimePad};
+///
+/// let spec = AlphabetSpec::new()
+///     .add_range('a'..='z')
+///     .add_range('0'..='9')
+///     .add_symbol('_');
+/// let otp = OneTimePad::new_with_spec(spec).unwrap();
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct AlphabetSpec {
+    symbols: Vec<char>,
+}
+
+impl AlphabetSpec {
+    /// Create a new, empty [`AlphabetSpec`].
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add a single symbol to the alphabet.
+    pub fn add_symbol(mut self, ch: char) -> Self {
+        self.symbols.push(ch);
+        self
+    }
+
+    /// Add every Unicode scalar value in a contiguous, inclusive range of
+    /// characters to the alphabet, in ascending order.
+    pub fn add_range(mut self, range: RangeInclusive<char>) -> Self {
+        self.symbols.extend(range);
+        self
+    }
+
+    /// Validate and compile this specification into the alphabet used
+    /// internally by [`OneTimePad`].
+    ///
+    /// Returns [`OneTimePadError::InvalidAlphabet`] if the alphabet contains
+    /// no symbols, or if the same symbol was added more than once.
+    fn build(self) -> Result<Alphabet> {
+        Alphabet::compile(self.symbols)
+    }
+}
+
+/// The compiled, validated form of an [`AlphabetSpec`], used internally by
+/// [`OneTimePad`] for O(1) lookups in both directions.
+#[derive(Clone, Debug)]
+struct Alphabet {
+    symbols: Vec<char>,
+    index: HashMap<char, usize>,
+}
+
+impl Alphabet {
+    fn compile(symbols: Vec<char>) -> Result<Self> {
+        if symbols.is_empty() {
+            return Err(OneTimePadError::InvalidAlphabet(
+                "the alphabet must contain at least one symbol".to_string(),
+            ));
+        }
+
+        let mut index = HashMap::with_capacity(symbols.len());
+        for (scalar, &ch) in symbols.iter().enumerate() {
+            if index.insert(ch, scalar).is_some() {
+                return Err(OneTimePadError::InvalidAlphabet(format!(
+                    "the character '{ch}' appears more than once in the alphabet"
+                )));
+            }
+        }
+
+        Ok(Self { symbols, index })
+    }
+
+    fn len(&self) -> usize {
+        self.symbols.len()
+    }
+
+    fn char_to_scalar(&self, ch: char) -> Option<usize> {
+        self.index.get(&ch).copied()
+    }
+
+    fn scalar_to_char(&self, sc: usize) -> char {
+        self.symbols[sc % self.symbols.len()]
+    }
+}
+
 /// A struct containing the state of a one time pad. It contains a buffer of
 /// pad characters which is used to encode and decode strings.
 ///
@@ -66,9 +214,9 @@ pub struct EncodingResult {
 /// let res = otp.decode("g2Vt1~.UjTq").unwrap();
 /// println!("{}", res);
 /// ```
-#[derive(Clone)]
+#[derive(Clone, Debug)]
 pub struct OneTimePad {
-    alphabet: String,
+    alphabet: Alphabet,
     pad_buffer: VecDeque<usize>,
 }
 
@@ -76,28 +224,55 @@ impl OneTimePad {
     /// Create a new [`OneTimePad`] instance with the default alphabet, which
     /// covers ASCII, except control characters.
     pub fn new() -> Self {
-        Self::new_with_alphabet(String::from(ASCII_ALPHABET))
+        Self::new_with_alphabet(ASCII_ALPHABET).expect("the default ASCII alphabet is valid")
     }
 
     /// Create a new [`OneTimePad`] instance with a custom alphabet. The first
     /// character in the string will be numbered 0, and the numeric
     /// representation will increase with the character index.
-    pub fn new_with_alphabet<S: AsRef<str>>(alphabet: S) -> Self {
-        Self {
-            alphabet: String::from(alphabet.as_ref()),
-            pad_buffer: VecDeque::new(),
+    ///
+    /// Returns [`OneTimePadError::InvalidAlphabet`] if `alphabet` is empty or
+    /// contains the same character more than once. To build an alphabet from
+    /// ranges of characters rather than a string, see [`AlphabetSpec`] and
+    /// [`OneTimePad::new_with_spec`].
+    pub fn new_with_alphabet<S: AsRef<str>>(alphabet: S) -> Result<Self> {
+        let mut spec = AlphabetSpec::new();
+        for ch in alphabet.as_ref().chars() {
+            spec = spec.add_symbol(ch);
         }
+        Self::new_with_spec(spec)
+    }
+
+    /// Create a new [`OneTimePad`] instance from a validated [`AlphabetSpec`].
+    ///
+    /// Returns [`OneTimePadError::InvalidAlphabet`] if the spec describes an
+    /// empty alphabet or one containing a duplicate symbol.
+    pub fn new_with_spec(spec: AlphabetSpec) -> Result<Self> {
+        Ok(Self {
+            alphabet: spec.build()?,
+            pad_buffer: VecDeque::new(),
+        })
+    }
+
+    /// Create a new [`OneTimePad`] instance in byte mode, where scalars
+    /// `0..256` map directly onto `u8` values instead of an alphabet of
+    /// printable characters. Use this with [`OneTimePad::encode_bytes`] and
+    /// [`OneTimePad::decode_bytes`] to work with arbitrary binary input, such
+    /// as images or compressed data, which may not consist of valid UTF-8 or
+    /// of characters in a printable alphabet.
+    pub fn new_bytes() -> Self {
+        let spec = AlphabetSpec::new().add_range('\u{0}'..='\u{ff}');
+        Self::new_with_spec(spec).expect("the 256-value byte alphabet is valid")
     }
 
     fn char_to_scalar(&self, ch: char) -> Result<usize> {
         self.alphabet
-            .find(ch)
+            .char_to_scalar(ch)
             .ok_or(OneTimePadError::CharacterNotInAlphabet(ch))
     }
 
     fn scalar_to_char(&self, sc: usize) -> char {
-        let sc = sc % self.alphabet.len();
-        self.alphabet.chars().nth(sc).unwrap()
+        self.alphabet.scalar_to_char(sc)
     }
 
     /// Push a string of characters to the end of the pad buffer. This will
@@ -110,9 +285,26 @@ impl OneTimePad {
         Ok(())
     }
 
+    /// Push a slice of raw bytes to the end of the pad buffer, for use with
+    /// [`OneTimePad::new_bytes`] byte-mode pads. Each byte is looked up as the
+    /// character with the same code point, and this will return a
+    /// [`OneTimePadError::CharacterNotInAlphabet`] error if any such character
+    /// is not in this pad's alphabet, which never happens in byte mode.
+    pub fn push_to_pad_bytes(&mut self, extra_pad_bytes: &[u8]) -> Result<()> {
+        for &b in extra_pad_bytes {
+            self.pad_buffer.push_back(self.char_to_scalar(b as char)?);
+        }
+        Ok(())
+    }
+
     #[cfg(feature = "rand")]
     /// Generate a random pad capable of encoding or decoding a string of the
     /// given size. The random generator is not guaranteed to be secure.
+    ///
+    /// A one-time pad's security depends entirely on its key material being
+    /// uniformly random, so this convenience should not be used for anything
+    /// that actually needs to stay secret. Prefer
+    /// [`OneTimePad::generate_pad_secure`] instead.
     pub fn generate_pad(&mut self, size: usize) {
         use rand::Rng;
         let mut rng = rand::thread_rng();
@@ -122,6 +314,55 @@ impl OneTimePad {
         }
     }
 
+    #[cfg(feature = "rand")]
+    /// Generate a pad of `size` characters drawn from the operating system's
+    /// cryptographically secure random number generator. Unlike
+    /// [`OneTimePad::generate_pad`], this is suitable for real one-time-pad
+    /// use.
+    pub fn generate_pad_secure(&mut self, size: usize) {
+        use rand::rngs::OsRng;
+        self.push_random_scalars(&mut OsRng, size);
+    }
+
+    #[cfg(feature = "rand")]
+    /// Generate a pad of `size` characters from an arbitrary seedable random
+    /// number generator `rng`. This is useful for producing reproducible
+    /// test vectors, or for deriving a shared pad between two parties who
+    /// agree on a seed out of band.
+    ///
+    /// Note that, unlike [`OneTimePad::generate_pad_secure`], the security of
+    /// the resulting pad depends entirely on the secrecy and randomness of
+    /// the seed used to initialise `rng`.
+    pub fn generate_pad_from_rng<R: rand::SeedableRng + rand::RngCore>(
+        &mut self,
+        rng: &mut R,
+        size: usize,
+    ) {
+        self.push_random_scalars(rng, size);
+    }
+
+    #[cfg(feature = "rand")]
+    /// Draw `size` scalars uniformly from `0..self.alphabet.len()` using
+    /// `rng`, appending them to the pad buffer.
+    ///
+    /// To avoid modulo bias, values are drawn via rejection sampling: the
+    /// largest multiple of `self.alphabet.len()` not exceeding `usize::MAX`
+    /// is used as a cutoff, and any draw at or above it is discarded.
+    fn push_random_scalars<R: rand::RngCore>(&mut self, rng: &mut R, size: usize) {
+        use rand::Rng;
+        let alphabet_len = self.alphabet.len();
+        let zone = usize::MAX - (usize::MAX % alphabet_len);
+        for _ in 0..size {
+            let scalar = loop {
+                let u: usize = rng.gen();
+                if u < zone {
+                    break u % alphabet_len;
+                }
+            };
+            self.pad_buffer.push_back(scalar);
+        }
+    }
+
     /// Empty the pad buffer completely.
     pub fn clear_pad(&mut self) {
         self.pad_buffer.clear();
@@ -140,7 +381,7 @@ impl OneTimePad {
     /// In the event that an error is returned, the pad will not have been
     /// changed.
     pub fn encode<S: AsRef<str>>(&mut self, plain_text: S) -> Result<EncodingResult> {
-        if self.pad_buffer.len() < plain_text.as_ref().len() {
+        if self.pad_buffer.len() < plain_text.as_ref().chars().count() {
             return Err(OneTimePadError::PadBufferNotLongEnough);
         }
         // Check before modifying pad
@@ -176,7 +417,7 @@ impl OneTimePad {
     /// In the event that an error is returned, the pad will not have been
     /// changed.
     pub fn decode<S: AsRef<str>>(&mut self, cipher_text: S) -> Result<String> {
-        if self.pad_buffer.len() < cipher_text.as_ref().len() {
+        if self.pad_buffer.len() < cipher_text.as_ref().chars().count() {
             return Err(OneTimePadError::PadBufferNotLongEnough);
         }
         // Check before modifying pad
@@ -193,6 +434,166 @@ impl OneTimePad {
         }
         Ok(plaintext)
     }
+
+    /// Encode a stream of plain text to ciphertext, reading from `reader` and
+    /// writing to `writer` in fixed-size chunks rather than buffering the
+    /// whole input in memory.
+    ///
+    /// The pad buffer is consumed as the stream is processed, exactly as it
+    /// would be by repeated calls to [`OneTimePad::encode`]. If the pad runs
+    /// dry partway through, a [`OneTimePadError::PadBufferNotLongEnough`] is
+    /// returned; note that, unlike `encode`, some ciphertext may already have
+    /// been written to `writer` by that point.
+    pub fn encode_stream<R: Read, W: Write>(&mut self, reader: R, writer: W) -> Result<()> {
+        self.stream_transform(reader, writer, |otp, chunk| {
+            otp.encode(chunk).map(|r| r.cipher_text)
+        })
+    }
+
+    /// Decode a stream of ciphertext to plain text, reading from `reader` and
+    /// writing to `writer` in fixed-size chunks rather than buffering the
+    /// whole input in memory.
+    ///
+    /// The pad buffer is consumed as the stream is processed, exactly as it
+    /// would be by repeated calls to [`OneTimePad::decode`]. If the pad runs
+    /// dry partway through, a [`OneTimePadError::PadBufferNotLongEnough`] is
+    /// returned; note that, unlike `decode`, some plain text may already have
+    /// been written to `writer` by that point.
+    pub fn decode_stream<R: Read, W: Write>(&mut self, reader: R, writer: W) -> Result<()> {
+        self.stream_transform(reader, writer, |otp, chunk| otp.decode(chunk))
+    }
+
+    /// Shared plumbing for [`OneTimePad::encode_stream`] and
+    /// [`OneTimePad::decode_stream`]: reads `reader` in fixed-size chunks of
+    /// up to [`STREAM_CHUNK_CHARS`] characters, taking care to never split a
+    /// multi-byte UTF-8 codepoint across a chunk boundary, and writes each
+    /// transformed chunk to `writer` as it's produced.
+    fn stream_transform<R, W, F>(
+        &mut self,
+        mut reader: R,
+        mut writer: W,
+        mut transform_chunk: F,
+    ) -> Result<()>
+    where
+        R: Read,
+        W: Write,
+        F: FnMut(&mut Self, &str) -> Result<String>,
+    {
+        let mut leftover_bytes = Vec::new();
+        let mut chunk = String::with_capacity(STREAM_CHUNK_CHARS);
+        let mut byte_buf = [0u8; STREAM_CHUNK_CHARS];
+
+        loop {
+            let n = reader.read(&mut byte_buf).map_err(OneTimePadError::Io)?;
+            let eof = n == 0;
+            leftover_bytes.extend_from_slice(&byte_buf[..n]);
+
+            let (valid_len, utf8_err) = match std::str::from_utf8(&leftover_bytes) {
+                Ok(s) => (s.len(), None),
+                Err(e) => (e.valid_up_to(), Some(e)),
+            };
+            chunk.push_str(std::str::from_utf8(&leftover_bytes[..valid_len]).unwrap());
+            leftover_bytes.drain(..valid_len);
+
+            // `error_len() == None` means the trailing bytes are merely an
+            // incomplete codepoint that could still be completed by the next
+            // read; anything else (a definite invalid sequence, or bytes
+            // still left over once the stream has ended) is a real error and
+            // must not be silently dropped.
+            if let Some(e) = utf8_err {
+                if e.error_len().is_some() || eof {
+                    return Err(OneTimePadError::InvalidUtf8(e));
+                }
+            }
+
+            while chunk.chars().count() >= STREAM_CHUNK_CHARS {
+                let split_at = chunk
+                    .char_indices()
+                    .nth(STREAM_CHUNK_CHARS)
+                    .map(|(i, _)| i)
+                    .unwrap_or(chunk.len());
+                let piece: String = chunk.drain(..split_at).collect();
+                let transformed = transform_chunk(self, &piece)?;
+                writer
+                    .write_all(transformed.as_bytes())
+                    .map_err(OneTimePadError::Io)?;
+            }
+
+            if eof {
+                if !chunk.is_empty() {
+                    let transformed = transform_chunk(self, &chunk)?;
+                    writer
+                        .write_all(transformed.as_bytes())
+                        .map_err(OneTimePadError::Io)?;
+                }
+                return Ok(());
+            }
+        }
+    }
+
+    /// Encode a slice of arbitrary bytes to ciphertext bytes, for use with
+    /// [`OneTimePad::new_bytes`] byte-mode pads.
+    ///
+    /// This has the same pad and alphabet requirements as
+    /// [`OneTimePad::encode`]: the pad buffer must contain at least as many
+    /// bytes as `plain_bytes`, and every byte value must be representable in
+    /// this pad's alphabet (which is always true in byte mode).
+    pub fn encode_bytes(&mut self, plain_bytes: &[u8]) -> Result<BytesEncodingResult> {
+        let plain_text: String = plain_bytes.iter().map(|&b| b as char).collect();
+        let result = self.encode(&plain_text)?;
+        Ok(BytesEncodingResult {
+            cipher_bytes: result.cipher_text.chars().map(|c| c as u8).collect(),
+            pad: result.pad.chars().map(|c| c as u8).collect(),
+        })
+    }
+
+    /// Decode a slice of ciphertext bytes to plain text bytes, for use with
+    /// [`OneTimePad::new_bytes`] byte-mode pads.
+    ///
+    /// This has the same pad and alphabet requirements as
+    /// [`OneTimePad::decode`].
+    pub fn decode_bytes(&mut self, cipher_bytes: &[u8]) -> Result<Vec<u8>> {
+        let cipher_text: String = cipher_bytes.iter().map(|&b| b as char).collect();
+        let plain_text = self.decode(&cipher_text)?;
+        Ok(plain_text.chars().map(|c| c as u8).collect())
+    }
+
+    #[cfg(feature = "base64")]
+    /// Encode a slice of arbitrary bytes and wrap the resulting ciphertext
+    /// (and the pad consumed to produce it) in standard base64, for safe
+    /// transmission as text. See [`OneTimePad::encode_bytes`].
+    pub fn encode_to_base64(&mut self, plain_bytes: &[u8]) -> Result<Base64EncodingResult> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        let result = self.encode_bytes(plain_bytes)?;
+        Ok(Base64EncodingResult {
+            cipher_text: STANDARD.encode(result.cipher_bytes),
+            pad: STANDARD.encode(result.pad),
+        })
+    }
+
+    #[cfg(feature = "base64")]
+    /// Decode a standard base64-encoded ciphertext produced by
+    /// [`OneTimePad::encode_to_base64`] back to plain text bytes. See
+    /// [`OneTimePad::decode_bytes`].
+    pub fn decode_from_base64<S: AsRef<str>>(&mut self, cipher_text: S) -> Result<Vec<u8>> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        let cipher_bytes = STANDARD
+            .decode(cipher_text.as_ref())
+            .map_err(OneTimePadError::Base64)?;
+        self.decode_bytes(&cipher_bytes)
+    }
+
+    #[cfg(feature = "base64")]
+    /// Decode a standard base64-encoded pad produced by
+    /// [`OneTimePad::encode_to_base64`] and push it to the end of the pad
+    /// buffer, for use with [`OneTimePad::decode_from_base64`].
+    pub fn push_to_pad_from_base64<S: AsRef<str>>(&mut self, pad: S) -> Result<()> {
+        use base64::{engine::general_purpose::STANDARD, Engine};
+        let pad_bytes = STANDARD
+            .decode(pad.as_ref())
+            .map_err(OneTimePadError::Base64)?;
+        self.push_to_pad_bytes(&pad_bytes)
+    }
 }
 
 #[cfg(test)]
@@ -227,7 +628,7 @@ mod tests {
         // X-Y:      020
         //           ACA
 
-        let mut otp = OneTimePad::new_with_alphabet("ABCDE");
+        let mut otp = OneTimePad::new_with_alphabet("ABCDE")?;
         otp.push_to_pad("BCD")?;
         let res = otp.encode("BED")?;
         assert_eq!(res.cipher_text, "ACA");
@@ -249,7 +650,7 @@ mod tests {
 
     #[test]
     fn test_char_not_in_alphabet() -> super::Result<()> {
-        let mut otp = OneTimePad::new_with_alphabet("ABCDE");
+        let mut otp = OneTimePad::new_with_alphabet("ABCDE")?;
         let res = otp.push_to_pad("WHOOPS");
         let err = res.expect_err("characters shouldn't have been valid in pad");
         match err {
@@ -258,4 +659,134 @@ mod tests {
         }
         Ok(())
     }
+
+    #[test]
+    fn test_duplicate_alphabet_character_rejected() {
+        let res = OneTimePad::new_with_alphabet("ABCDA");
+        let err = res.expect_err("duplicate character should have been rejected");
+        match err {
+            crate::OneTimePadError::InvalidAlphabet(_) => (),
+            _ => panic!("this shouldn't be the returned error!"),
+        }
+    }
+
+    #[test]
+    fn test_empty_alphabet_rejected() {
+        let res = OneTimePad::new_with_alphabet("");
+        let err = res.expect_err("empty alphabet should have been rejected");
+        match err {
+            crate::OneTimePadError::InvalidAlphabet(_) => (),
+            _ => panic!("this shouldn't be the returned error!"),
+        }
+    }
+
+    #[test]
+    fn test_alphabet_spec_ranges_and_symbols() -> super::Result<()> {
+        let spec = crate::AlphabetSpec::new()
+            .add_range('a'..='e')
+            .add_symbol('_');
+        let mut otp = OneTimePad::new_with_spec(spec)?;
+        otp.push_to_pad("ab_")?;
+        let res = otp.encode("cde")?;
+        assert_eq!(res.cipher_text, "cc_");
+        Ok(())
+    }
+
+    #[test]
+    fn test_byte_mode_round_trip() -> super::Result<()> {
+        let plain_bytes: Vec<u8> = vec![0, 1, 127, 128, 200, 255];
+
+        let mut encoder = OneTimePad::new_bytes();
+        encoder.push_to_pad_bytes(&[9, 8, 7, 6, 5, 4])?;
+        let result = encoder.encode_bytes(&plain_bytes)?;
+
+        let mut decoder = OneTimePad::new_bytes();
+        decoder.push_to_pad_bytes(&result.pad)?;
+        let decoded = decoder.decode_bytes(&result.cipher_bytes)?;
+
+        assert_eq!(decoded, plain_bytes);
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_byte_alphabet_length_check() -> super::Result<()> {
+        // é, λ and 😀 all have UTF-8 byte lengths greater than 1, so a
+        // length check comparing pad scalars against `str::len()` (bytes)
+        // rather than `chars().count()` would wrongly reject this even
+        // though the pad has exactly enough scalars for the 3-character
+        // input.
+        let mut otp = OneTimePad::new_with_alphabet("éλ😀")?;
+        otp.push_to_pad("λ😀é")?;
+        let res = otp.encode("é😀λ")?;
+        assert_eq!(res.cipher_text, "😀éλ");
+        Ok(())
+    }
+
+    #[test]
+    fn test_multi_byte_alphabet_pad_too_short() -> super::Result<()> {
+        let mut otp = OneTimePad::new_with_alphabet("éλ😀")?;
+        otp.push_to_pad("λ😀")?;
+        let res = otp.encode("é😀λ");
+        let err = res.expect_err("pad shouldn't have been long enough");
+        match err {
+            crate::OneTimePadError::PadBufferNotLongEnough => (),
+            _ => panic!("this shouldn't be the returned error!"),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_round_trip() -> super::Result<()> {
+        let mut encoder = OneTimePad::new();
+        encoder.push_to_pad("8t5l!Ok2v$q4e3/S3dOLztDY")?;
+        let plain_text = "Never gonna give you up.";
+        let mut cipher_bytes = Vec::new();
+        encoder.encode_stream(plain_text.as_bytes(), &mut cipher_bytes)?;
+
+        let mut decoder = OneTimePad::new();
+        decoder.push_to_pad("8t5l!Ok2v$q4e3/S3dOLztDY")?;
+        let mut plain_bytes = Vec::new();
+        decoder.decode_stream(cipher_bytes.as_slice(), &mut plain_bytes)?;
+
+        assert_eq!(String::from_utf8(plain_bytes).unwrap(), plain_text);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_pad_exhausted_mid_stream() -> super::Result<()> {
+        // The pad covers the first full STREAM_CHUNK_CHARS-sized chunk and
+        // only 4 characters of the 900-character second chunk, so it should
+        // run dry partway through the second chunk.
+        let mut otp = OneTimePad::new_with_alphabet("A")?;
+        otp.push_to_pad("A".repeat(super::STREAM_CHUNK_CHARS + 4))?;
+        let plain_text = "A".repeat(super::STREAM_CHUNK_CHARS + 900);
+
+        let mut cipher_bytes = Vec::new();
+        let res = otp.encode_stream(plain_text.as_bytes(), &mut cipher_bytes);
+        let err = res.expect_err("pad should have run dry partway through the stream");
+        match err {
+            crate::OneTimePadError::PadBufferNotLongEnough => (),
+            _ => panic!("this shouldn't be the returned error!"),
+        }
+        // The first full chunk should already have been written before the
+        // pad ran dry.
+        assert_eq!(cipher_bytes.len(), super::STREAM_CHUNK_CHARS);
+        Ok(())
+    }
+
+    #[test]
+    fn test_stream_rejects_invalid_utf8() -> super::Result<()> {
+        let mut otp = OneTimePad::new();
+        otp.push_to_pad("8t5l!Ok2v$q4e3/S3dOLztDY")?;
+        let invalid_utf8 = [b'N', b'e', 0xff, 0xfe, b'r'];
+
+        let mut out = Vec::new();
+        let res = otp.encode_stream(&invalid_utf8[..], &mut out);
+        let err = res.expect_err("invalid UTF-8 should have been rejected");
+        match err {
+            crate::OneTimePadError::InvalidUtf8(_) => (),
+            _ => panic!("this shouldn't be the returned error!"),
+        }
+        Ok(())
+    }
 }
@@ -41,7 +41,13 @@ fn main() {
     let cli = Cli::parse();
 
     let mut one_time_pad = if let Some(alphabet) = cli.alphabet {
-        OneTimePad::new_with_alphabet(alphabet)
+        match OneTimePad::new_with_alphabet(alphabet) {
+            Ok(otp) => otp,
+            Err(e) => {
+                eprintln!("Invalid alphabet: {e}");
+                process::exit(1);
+            }
+        }
     } else {
         OneTimePad::new()
     };